@@ -2,15 +2,16 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use anyhow::Result;
+use async_trait::async_trait;
 use futures_util::StreamExt;
 use http::Uri;
 use serde::Deserialize;
 use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
-use tokio::time::{sleep, timeout};
+use tokio::time::timeout;
 use tokio_websockets::ClientBuilder;
-use tracing::{error, info};
+use tracing::info;
 
+use crate::exchanges::ExchangeSource;
 use crate::order_book::{parse_data, OrderBookUpdate};
 
 #[derive(Deserialize, Debug)]
@@ -37,20 +38,15 @@ impl BinanceExchangeSource {
         )
     }
 
-    pub fn begin(self, sender: mpsc::Sender<OrderBookUpdate>) -> JoinHandle<()> {
-        tokio::spawn(async move {
-            loop {
-                match self.connect(sender.clone()).await {
-                    Err(e) => error!("Worker task errored, retrying: {}", e),
-                    Ok(_) => error!("Worker task exited, reconnecting"),
-                };
+}
 
-                sleep(Duration::from_secs(2)).await;
-            }
-        })
+#[async_trait]
+impl ExchangeSource for BinanceExchangeSource {
+    fn name(&self) -> &str {
+        "binance"
     }
 
-    pub async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()> {
+    async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()> {
         let uri = Uri::from_str(&self.url())?;
         let (mut client, _) = ClientBuilder::from_uri(uri).connect().await?;
 
@@ -75,11 +71,11 @@ mod tests {
     #[tokio::test]
     async fn test_connect() {
         let (sender, mut receiver) = mpsc::channel(64);
-        let bitstamp = BinanceExchangeSource {
+        let binance = BinanceExchangeSource {
             currency_pair: "btcusdt".to_owned(),
         };
 
-        let _handle = bitstamp.begin(sender);
+        let _handle = Box::new(binance).begin(sender);
 
         let mut received_updates = Vec::new();
 
@@ -1,15 +1,16 @@
 use std::time::Duration;
 
 use anyhow::Result;
+use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use http::Uri;
 use serde::Deserialize;
 use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
-use tokio::time::{sleep, timeout};
+use tokio::time::timeout;
 use tokio_websockets::{ClientBuilder, Message};
-use tracing::{error, info};
+use tracing::info;
 
+use crate::exchanges::ExchangeSource;
 use crate::order_book::{parse_data, OrderBookUpdate};
 
 #[derive(Debug, Deserialize)]
@@ -39,20 +40,15 @@ impl BitstampExchangeSource {
         BitstampExchangeSource { currency_pair }
     }
 
-    pub fn begin(self, sender: mpsc::Sender<OrderBookUpdate>) -> JoinHandle<()> {
-        tokio::spawn(async move {
-            loop {
-                match self.connect(sender.clone()).await {
-                    Err(e) => error!("Worker task errored, retrying: {}", e),
-                    Ok(_) => error!("Worker task exited, reconnecting"),
-                };
+}
 
-                sleep(Duration::from_secs(2)).await;
-            }
-        })
+#[async_trait]
+impl ExchangeSource for BitstampExchangeSource {
+    fn name(&self) -> &str {
+        "bitstamp"
     }
 
-    pub async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()> {
+    async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()> {
         let uri = Uri::from_static("wss://ws.bitstamp.net");
         let (mut client, _) = ClientBuilder::from_uri(uri).connect().await?;
 
@@ -101,7 +97,7 @@ mod tests {
             currency_pair: "btcusdt".to_owned(),
         };
 
-        let _handle = bitstamp.begin(sender);
+        let _handle = Box::new(bitstamp).begin(sender);
 
         let mut received_updates = Vec::new();
 
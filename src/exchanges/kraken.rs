@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use http::Uri;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tokio_websockets::{ClientBuilder, Message};
+use tracing::error;
+
+use crate::exchanges::ExchangeSource;
+use crate::order_book::{parse_data, OrderBookUpdate};
+
+/// Per-pair formatting precision used when rebuilding the checksum string.
+///
+/// Kraken computes the book checksum from the price and volume rendered at the
+/// pair's configured decimals, so the values here must match the venue's
+/// `tick_size`/`lot_decimals` for the subscribed pair.
+#[derive(Debug, Clone, Copy)]
+struct PairPrecision {
+    price: u32,
+    volume: u32,
+}
+
+impl PairPrecision {
+    /// Returns the checksum precision for `pair`, or `None` for a pair we have
+    /// no configured precision for. Guessing would silently compute wrong
+    /// checksums, so callers must treat an unknown pair as an error rather than
+    /// fall back to a default.
+    fn for_pair(pair: &str) -> Option<PairPrecision> {
+        match pair {
+            // Kraken quotes XBT/USD to one decimal and volumes to eight.
+            "XBT/USD" => Some(PairPrecision { price: 1, volume: 8 }),
+            _ => None,
+        }
+    }
+}
+
+/// A delta-based source for Kraken's `book` channel.
+///
+/// Unlike the snapshot-only sources, Kraken sends an initial `as`/`bs` snapshot
+/// followed by `a`/`b` deltas. We keep a local copy of the book, apply each
+/// delta, validate the running CRC32 checksum Kraken appends, and forward the
+/// merged top-`depth` levels through `parse_data` like the other sources.
+pub struct KrakenExchangeSource {
+    currency_pair: String,
+    depth: usize,
+}
+
+impl KrakenExchangeSource {
+    pub fn new(currency_pair: String) -> KrakenExchangeSource {
+        KrakenExchangeSource {
+            currency_pair,
+            depth: 10,
+        }
+    }
+
+    /// Kraken expects `XBT/USD` style pairs, whereas the other venues take a
+    /// lowercased concatenation. Map the common case so `main` can keep passing
+    /// a single pair string.
+    fn pair(&self) -> String {
+        match self.currency_pair.as_str() {
+            "btcusd" | "btcusdt" | "xbtusd" => "XBT/USD".to_owned(),
+            other => other.to_uppercase(),
+        }
+    }
+
+}
+
+#[async_trait]
+impl ExchangeSource for KrakenExchangeSource {
+    fn name(&self) -> &str {
+        "kraken"
+    }
+
+    async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()> {
+        let uri = Uri::from_static("wss://ws.kraken.com");
+        let (mut client, _) = ClientBuilder::from_uri(uri).connect().await?;
+
+        let pair = self.pair();
+        let precision = PairPrecision::for_pair(&pair)
+            .ok_or_else(|| anyhow!("no checksum precision configured for Kraken pair {}", pair))?;
+        let subscribe_msg = format!(
+            r#"{{"event":"subscribe","subscription":{{"name":"book","depth":{}}},"pair":["{}"]}}"#,
+            self.depth, pair
+        );
+        client.send(Message::text(subscribe_msg.clone())).await?;
+
+        let mut book = LocalBook::new(self.depth, precision);
+
+        while let Some(res) = timeout(Duration::from_secs(15), client.next()).await? {
+            let res = res?;
+            let Some(text) = res.as_text() else { continue };
+
+            let value: Value = serde_json::from_str(text)?;
+
+            // Status/heartbeat payloads are objects; book updates are arrays.
+            let Some(array) = value.as_array() else {
+                continue;
+            };
+
+            match book.apply(array) {
+                Ok(true) => {
+                    let (bids, asks) = book.levels();
+                    let update = parse_data(bids, asks, "kraken")?;
+                    sender.send(update).await?;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    // Data-integrity failure: drop the local book and resubscribe
+                    // on the same connection so we recover promptly, rather than
+                    // tearing down and waiting out the reconnect backoff.
+                    error!("Kraken book checksum failed ({}), resubscribing", e);
+                    book = LocalBook::new(self.depth, precision);
+                    client.send(Message::text(subscribe_msg.clone())).await?;
+                }
+            }
+        }
+
+        Result::Ok(())
+    }
+}
+
+/// The locally maintained Kraken book.
+///
+/// Bids and asks are kept in `BTreeMap`s keyed by price so deltas can
+/// insert/replace/delete a single level cheaply while staying ordered.
+struct LocalBook {
+    bids: BTreeMap<Decimal, String>,
+    asks: BTreeMap<Decimal, String>,
+    depth: usize,
+    precision: PairPrecision,
+    initialized: bool,
+}
+
+impl LocalBook {
+    fn new(depth: usize, precision: PairPrecision) -> LocalBook {
+        LocalBook {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            depth,
+            precision,
+            initialized: false,
+        }
+    }
+
+    /// Applies one websocket frame. Returns `Ok(true)` when the book changed and
+    /// should be forwarded, `Ok(false)` for frames that carry no levels or that
+    /// arrive before the initial snapshot, and an error when a level or checksum
+    /// cannot be parsed or the validated checksum does not match the computed one.
+    fn apply(&mut self, array: &[Value]) -> Result<bool> {
+        let mut changed = false;
+        let mut checksum: Option<u32> = None;
+
+        for entry in array {
+            let Some(obj) = entry.as_object() else {
+                continue;
+            };
+
+            if let Some(snapshot) = obj.get("as") {
+                self.asks.clear();
+                self.apply_levels(snapshot, false)?;
+                self.initialized = true;
+                changed = true;
+            }
+            if let Some(snapshot) = obj.get("bs") {
+                self.bids.clear();
+                self.apply_levels(snapshot, true)?;
+                self.initialized = true;
+                changed = true;
+            }
+            if let Some(deltas) = obj.get("a") {
+                self.apply_levels(deltas, false)?;
+                changed = true;
+            }
+            if let Some(deltas) = obj.get("b") {
+                self.apply_levels(deltas, true)?;
+                changed = true;
+            }
+            if let Some(c) = obj.get("c").and_then(Value::as_str) {
+                checksum = Some(c.parse()?);
+            }
+        }
+
+        if !changed {
+            return Ok(false);
+        }
+
+        self.truncate();
+
+        if let Some(expected) = checksum {
+            let actual = self.checksum();
+            if actual != expected {
+                return Err(anyhow!("expected {}, computed {}", expected, actual));
+            }
+        }
+
+        Ok(self.initialized)
+    }
+
+    /// Inserts/replaces or deletes each level in a Kraken `[price, volume, ..]`
+    /// array. A volume of `0` removes the level.
+    fn apply_levels(&mut self, levels: &Value, is_bid: bool) -> Result<()> {
+        let levels = levels
+            .as_array()
+            .ok_or_else(|| anyhow!("expected level array"))?;
+
+        for level in levels {
+            let price_str = level
+                .get(0)
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("missing price"))?;
+            let volume_str = level
+                .get(1)
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("missing volume"))?;
+
+            let price = Decimal::from_str(price_str)?;
+            let volume = Decimal::from_str(volume_str)?;
+
+            let side = if is_bid { &mut self.bids } else { &mut self.asks };
+            if volume.is_zero() {
+                side.remove(&price);
+            } else {
+                side.insert(price, volume_str.to_owned());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keeps only the top `depth` levels per side: asks ascending, bids
+    /// descending.
+    fn truncate(&mut self) {
+        while self.asks.len() > self.depth {
+            if let Some((&price, _)) = self.asks.iter().next_back() {
+                self.asks.remove(&price);
+            }
+        }
+        while self.bids.len() > self.depth {
+            if let Some((&price, _)) = self.bids.iter().next() {
+                self.bids.remove(&price);
+            }
+        }
+    }
+
+    /// The merged top-`depth` levels as `(price, amount)` string pairs, ordered
+    /// the way the other sources emit them (asks ascending, bids descending).
+    fn levels(&self) -> (Vec<(String, String)>, Vec<(String, String)>) {
+        let bids = self
+            .bids
+            .iter()
+            .rev()
+            .take(self.depth)
+            .map(|(price, volume)| (price.to_string(), volume.clone()))
+            .collect();
+        let asks = self
+            .asks
+            .iter()
+            .take(self.depth)
+            .map(|(price, volume)| (price.to_string(), volume.clone()))
+            .collect();
+        (bids, asks)
+    }
+
+    /// Reproduces Kraken's book checksum: the top 10 asks (ascending) then the
+    /// top 10 bids (descending), each contributing price and volume rendered at
+    /// the pair precision with the decimal point removed and leading zeros
+    /// stripped, concatenated and run through CRC32 (IEEE).
+    fn checksum(&self) -> u32 {
+        let mut payload = String::new();
+
+        for (price, volume) in self.asks.iter().take(10) {
+            payload.push_str(&checksum_field(*price, self.precision.price));
+            payload.push_str(&checksum_field_str(volume, self.precision.volume));
+        }
+        for (price, volume) in self.bids.iter().rev().take(10) {
+            payload.push_str(&checksum_field(*price, self.precision.price));
+            payload.push_str(&checksum_field_str(volume, self.precision.volume));
+        }
+
+        crc32fast::hash(payload.as_bytes())
+    }
+}
+
+fn checksum_field(value: Decimal, decimals: u32) -> String {
+    checksum_field_str(&value.to_string(), decimals)
+}
+
+/// Formats a numeric string at `decimals` places, drops the decimal point, and
+/// strips the leading zeros, matching Kraken's checksum encoding.
+fn checksum_field_str(value: &str, decimals: u32) -> String {
+    let parsed = Decimal::from_str(value).unwrap_or_default();
+    let fixed = format!("{:.*}", decimals as usize, parsed);
+    let digits: String = fixed.chars().filter(|c| *c != '.').collect();
+    let trimmed = digits.trim_start_matches('0');
+    if trimmed.is_empty() {
+        "0".to_owned()
+    } else {
+        trimmed.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_connect() {
+        let (sender, mut receiver) = mpsc::channel(64);
+        let kraken = KrakenExchangeSource {
+            currency_pair: "btcusd".to_owned(),
+            depth: 10,
+        };
+
+        let _handle = Box::new(kraken).begin(sender);
+
+        let mut received_updates = Vec::new();
+
+        for _ in 0..5 {
+            if let Some(update) = receiver.recv().await {
+                received_updates.push(update);
+            } else {
+                break;
+            }
+        }
+
+        assert!(!received_updates.is_empty());
+    }
+
+    #[test]
+    fn test_checksum_and_delta() {
+        let precision = PairPrecision::for_pair("XBT/USD").unwrap();
+        let mut book = LocalBook::new(10, precision);
+
+        let snapshot = serde_json::json!([
+            0,
+            {
+                "as": [["34000.1", "1.5", "t"], ["34000.2", "2.0", "t"]],
+                "bs": [["33999.9", "0.5", "t"], ["33999.8", "1.0", "t"]]
+            },
+            "book-10",
+            "XBT/USD"
+        ]);
+        assert!(book.apply(snapshot.as_array().unwrap()).unwrap());
+
+        // Delta: a volume of "0" deletes the 33999.9 bid, and 33999.7 is inserted.
+        let delta = serde_json::json!([
+            0,
+            { "b": [["33999.9", "0", "t"], ["33999.7", "2.5", "t"]] },
+            "XBT/USD"
+        ]);
+        assert!(book.apply(delta.as_array().unwrap()).unwrap());
+
+        assert!(!book.bids.contains_key(&Decimal::from_str("33999.9").unwrap()));
+        assert!(book.bids.contains_key(&Decimal::from_str("33999.7").unwrap()));
+
+        // CRC32 (IEEE) of the concatenated top-of-book price/volume fields.
+        assert_eq!(book.checksum(), 3799463572);
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rand::Rng;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+use tracing::error;
+
+use crate::order_book::OrderBookUpdate;
+
+pub mod binance;
+pub mod bitstamp;
+pub mod kraken;
+
+/// A single venue feeding `OrderBookUpdate`s into the merge layer.
+///
+/// Every source only has to say what it is called and how to open one
+/// connection; the shared [`ExchangeSource::begin`] drives the
+/// spawn-and-reconnect loop with exponential backoff so adding a venue is a
+/// matter of implementing one method.
+#[async_trait]
+pub trait ExchangeSource: Send + Sync + 'static {
+    /// The venue's short name, used for logging and as the `exchange` tag.
+    fn name(&self) -> &str;
+
+    /// Opens one connection and streams updates until it closes or errors.
+    async fn connect(&self, sender: mpsc::Sender<OrderBookUpdate>) -> Result<()>;
+
+    /// Spawns the reconnecting driver loop and returns its handle. Callers hold
+    /// sources as `Box<dyn ExchangeSource>` and start them uniformly.
+    fn begin(self: Box<Self>, sender: mpsc::Sender<OrderBookUpdate>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut backoff = Backoff::default();
+            loop {
+                let started = Instant::now();
+                match self.connect(sender.clone()).await {
+                    Err(e) => error!("{} worker errored, retrying: {}", self.name(), e),
+                    Ok(_) => error!("{} worker exited, reconnecting", self.name()),
+                };
+
+                // A connection that stayed up past the threshold was healthy,
+                // not flapping — reset so only rapid repeated failures escalate
+                // toward the cap instead of a single blip pinning us there.
+                if started.elapsed() >= Backoff::HEALTHY_THRESHOLD {
+                    backoff = Backoff::default();
+                }
+
+                sleep(backoff.next_delay()).await;
+            }
+        })
+    }
+}
+
+/// Exponential backoff with full jitter for the reconnect loop, so flapping
+/// connections ramp from the initial delay up to the cap instead of hammering a
+/// venue every two seconds.
+struct Backoff {
+    current: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    const INITIAL: Duration = Duration::from_secs(2);
+    const MAX: Duration = Duration::from_secs(60);
+    /// A connection that stayed up at least this long counts as healthy and
+    /// resets the backoff.
+    const HEALTHY_THRESHOLD: Duration = Duration::from_secs(60);
+
+    /// Returns a jittered delay and doubles the base for next time.
+    fn next_delay(&mut self) -> Duration {
+        let jittered = rand::thread_rng().gen_range(0..=self.current.as_millis() as u64);
+        self.current = (self.current * 2).min(self.max);
+        Duration::from_millis(jittered)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            current: Self::INITIAL,
+            max: Self::MAX,
+        }
+    }
+}
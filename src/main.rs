@@ -1,14 +1,50 @@
 mod order_book;
 mod exchanges;
+mod persistence;
+mod server;
 
+use std::collections::HashMap;
 use std::env;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
 
 use exchanges::binance::BinanceExchangeSource;
 use exchanges::bitstamp::BitstampExchangeSource;
+use exchanges::kraken::KrakenExchangeSource;
+use exchanges::ExchangeSource;
 use order_book::MergedOrderBook;
+use persistence::{now_secs, Persistence};
+use server::MarketData;
 use tokio::sync::mpsc;
 use tracing::info;
 
+/// Parses `exchange=taker_fee` CLI arguments into a fee map. A malformed entry
+/// aborts startup rather than silently dropping a fee.
+fn parse_fees(args: &[String]) -> HashMap<String, Decimal> {
+    let mut fees = HashMap::new();
+    for arg in args {
+        let Some((exchange, rate)) = arg.split_once('=') else {
+            eprintln!("Invalid fee argument '{}', expected exchange=taker_fee", arg);
+            std::process::exit(1);
+        };
+        let rate = Decimal::from_str(rate).unwrap_or_else(|_| {
+            eprintln!("Invalid fee rate '{}' for {}", rate, exchange);
+            std::process::exit(1);
+        });
+        fees.insert(exchange.to_owned(), rate);
+    }
+    fees
+}
+
+/// Candle width in seconds, overridable via `CANDLE_INTERVAL_SECS`.
+fn candle_interval() -> i64 {
+    env::var("CANDLE_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60)
+}
 
 #[tokio::main]
 async fn main() {
@@ -17,22 +53,68 @@ async fn main() {
 
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 2 {
-        eprintln!("Usage: {} <currency_pair>", args[0]);
+    // `backfill <from> <to>` regenerates candles from the raw snapshots table
+    // (requires DATABASE_URL) instead of starting the live feed.
+    if args.get(1).map(String::as_str) == Some("backfill") {
+        if args.len() != 4 {
+            eprintln!("Usage: {} backfill <from_unix_secs> <to_unix_secs>", args[0]);
+            std::process::exit(1);
+        }
+        let from: i64 = args[2].parse().expect("invalid from timestamp");
+        let to: i64 = args[3].parse().expect("invalid to timestamp");
+        let url = env::var("DATABASE_URL").expect("DATABASE_URL must be set for backfill");
+        let persistence = Persistence::connect(&url, candle_interval())
+            .await
+            .expect("Cannot connect to Postgres");
+        persistence.backfill(from, to).await.expect("backfill failed");
+        return;
+    }
+
+    if args.len() < 2 {
+        eprintln!("Usage: {} <currency_pair> [exchange=taker_fee ...]", args[0]);
         std::process::exit(1);
     }
 
     let currency_pair = args[1].clone();
 
+    // Optional per-exchange taker fees, e.g. `binance=0.001 kraken=0.0016`.
+    let fees = parse_fees(&args[2..]);
+
+    // Persist candles only when a database is configured; otherwise updates are
+    // only published to the server below.
+    let mut persistence = match env::var("DATABASE_URL") {
+        Ok(url) => Some(
+            Persistence::connect(&url, candle_interval())
+                .await
+                .expect("Cannot connect to Postgres"),
+        ),
+        Err(_) => None,
+    };
+
     let (sender, mut receiver) = mpsc::channel(64);
 
-    let binance = BinanceExchangeSource::new(currency_pair.clone());
-    binance.begin(sender.clone());
+    let sources: Vec<Box<dyn ExchangeSource>> = vec![
+        Box::new(BinanceExchangeSource::new(currency_pair.clone())),
+        Box::new(BitstampExchangeSource::new(currency_pair.clone())),
+        Box::new(KrakenExchangeSource::new(currency_pair.clone())),
+    ];
 
-    let bitstamp = BitstampExchangeSource::new(currency_pair.clone());
-    bitstamp.begin(sender);
+    for source in sources {
+        source.begin(sender.clone());
+    }
+    drop(sender);
 
     let mut order_book = MergedOrderBook::new(10);
+    order_book.set_fees(fees);
+
+    // Expose the merged book over HTTP (`/tickers`, `/summary`) and an SSE
+    // `/stream`, decoupling output from the ingestion loop.
+    let serve_addr: SocketAddr = env::var("SERVE_ADDR")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| SocketAddr::from(([127, 0, 0, 1], 8080)));
+    let market_data = MarketData::new();
+    market_data.serve(serve_addr);
 
     loop {
         let msg = receiver.recv().await;
@@ -40,12 +122,17 @@ async fn main() {
         if let Some(update) = msg {
             info!("Received order book update for: {}", update.exchange);
 
-            let mut result = order_book.update(update);
-            result.normalize(2, 4, 3);
+            let result = order_book.update(update);
 
-            let json = serde_json::to_string_pretty(&result).unwrap();
+            // Persist from the un-rounded result so candles keep full precision.
+            if let Some(persistence) = &mut persistence {
+                if let Err(e) = persistence.ingest(&result, now_secs()).await {
+                    info!("Failed to persist update: {}", e);
+                }
+            }
 
-            println!("{}", json);
+            // Publish to HTTP/stream consumers; rounding happens server-side.
+            market_data.publish(result).await;
         }
     }
 }
@@ -1,6 +1,10 @@
 use rust_decimal::Decimal;
 use serde::Serialize;
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    str::FromStr,
+};
 use anyhow::Result;
 
 #[derive(Debug, Clone)]
@@ -22,25 +26,31 @@ pub struct OrderBookUpdate {
     pub asks: Vec<Ask>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct ExchangeAsk {
     exchange: String,
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
     price: Decimal,
+    // The fee-adjusted price the level costs a taker; merging ranks on this.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    effective_price: Decimal,
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
     amount: Decimal
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct ExchangeBid {
     exchange: String,
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
     price: Decimal,
+    // The fee-adjusted price the level yields a taker; merging ranks on this.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    effective_price: Decimal,
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
     amount: Decimal
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct UpdateResult {
     pub asks: Vec<ExchangeAsk>,
     pub bids: Vec<ExchangeBid>,
@@ -48,6 +58,47 @@ pub struct UpdateResult {
 }
 
 impl UpdateResult {
+    /// The best (lowest) raw ask price across venues, if any.
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|ask| ask.price)
+    }
+
+    /// The best (highest) raw bid price across venues, if any.
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|bid| bid.price)
+    }
+
+    /// The best fee-adjusted ask price across venues, if any.
+    pub fn best_effective_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|ask| ask.effective_price)
+    }
+
+    /// The best fee-adjusted bid price across venues, if any.
+    pub fn best_effective_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|bid| bid.effective_price)
+    }
+
+    /// The mid-price between the best bid and ask, if both sides are present.
+    ///
+    /// Computed on the fee-adjusted prices so the mid shares the same basis as
+    /// [`UpdateResult::spread`] — otherwise a persisted candle would mix a raw
+    /// mid with a fee-adjusted spread.
+    pub fn mid(&self) -> Option<Decimal> {
+        match (self.best_effective_ask(), self.best_effective_bid()) {
+            (Some(ask), Some(bid)) => Some((ask + bid) / Decimal::TWO),
+            _ => None,
+        }
+    }
+
+    /// The distinct venues contributing to the merged top-N levels.
+    pub fn exchanges(&self) -> BTreeSet<String> {
+        self.asks
+            .iter()
+            .map(|ask| ask.exchange.clone())
+            .chain(self.bids.iter().map(|bid| bid.exchange.clone()))
+            .collect()
+    }
+
     pub fn normalize(
         &mut self,
         price_decimal_places: u32,
@@ -60,11 +111,13 @@ impl UpdateResult {
 
         for ask in &mut self.asks {
             ask.price = normalize_decimal(ask.price, price_decimal_places);
+            ask.effective_price = normalize_decimal(ask.effective_price, price_decimal_places);
             ask.amount = normalize_decimal(ask.amount, amount_decimal_places);
         }
 
         for bid in &mut self.bids {
             bid.price = normalize_decimal(bid.price, price_decimal_places);
+            bid.effective_price = normalize_decimal(bid.effective_price, price_decimal_places);
             bid.amount = normalize_decimal(bid.amount, amount_decimal_places);
         }
 
@@ -74,10 +127,45 @@ impl UpdateResult {
     }
 }
 
+/// One cursor into an exchange's ordered level list, ranked by its fee-adjusted
+/// `key`. `src` indexes the list of exchanges being merged and `idx` the
+/// position within that exchange's levels.
+#[derive(PartialEq, Eq)]
+struct HeapEntry {
+    key: Decimal,
+    src: usize,
+    idx: usize,
+}
+
+impl HeapEntry {
+    fn new(key: Decimal, src: usize, idx: usize) -> HeapEntry {
+        HeapEntry { key, src, idx }
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Ordering is by ranking key; `src`/`idx` break ties deterministically.
+        self.key
+            .cmp(&other.key)
+            .then(self.src.cmp(&other.src))
+            .then(self.idx.cmp(&other.idx))
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug)]
 pub struct MergedOrderBook {
     latest_bids: HashMap<String, Vec<Bid>>,
     latest_asks: HashMap<String, Vec<Ask>>,
+    // Per-exchange taker fee as a fraction (e.g. 0.001 for 10 bps); missing
+    // venues are treated as fee-free.
+    fees: HashMap<String, Decimal>,
     top_n: usize,
 }
 
@@ -86,28 +174,64 @@ impl MergedOrderBook {
         MergedOrderBook {
             latest_bids: HashMap::new(),
             latest_asks: HashMap::new(),
+            fees: HashMap::new(),
             top_n,
         }
     }
 
+    /// Sets the per-exchange taker-fee map used to adjust prices before merging.
+    pub fn set_fees(&mut self, fees: HashMap<String, Decimal>) {
+        self.fees = fees;
+    }
+
+    fn fee(&self, exchange: &str) -> Decimal {
+        self.fees.get(exchange).copied().unwrap_or(Decimal::ZERO)
+    }
+
     // Updates the latest bid snapshot of the exchange and returns top 10 combined bids
     // Assumes bids are already sorted and in correct order
     fn update_bids(&mut self, exchange: &str, bids: Vec<Bid>) -> Vec<ExchangeBid> {
         self.latest_bids.insert(exchange.to_string(), bids);
 
-        let mut all_bids: Vec<ExchangeBid> = Vec::new();
-
-        for (exchange, bids) in &mut self.latest_bids {
-            let list: Vec<ExchangeBid> = bids
-                .into_iter()
-                .map(|b| ExchangeBid { exchange: exchange.clone(), price: b.price, amount: b.amount } )
-                .collect();
-            all_bids.extend(list.into_iter().take(self.top_n));
+        // Each exchange's bids are already ordered by descending price, and the
+        // fee is constant per venue, so they are ordered by descending effective
+        // price too. Merge the k pre-ordered lists with a max-heap instead of
+        // rebuilding and sorting every level on each tick.
+        let sources: Vec<(&String, &Vec<Bid>, Decimal)> = self
+            .latest_bids
+            .iter()
+            .map(|(exchange, bids)| (exchange, bids, self.fee(exchange)))
+            .collect();
+
+        let mut heap: BinaryHeap<HeapEntry> = BinaryHeap::new();
+        for (src, (_, bids, fee)) in sources.iter().enumerate() {
+            if let Some(bid) = bids.first() {
+                // A bid is worth less after the taker fee is deducted.
+                heap.push(HeapEntry::new(bid.price * (Decimal::ONE - *fee), src, 0));
+            }
         }
 
-        all_bids.sort_by(|a, b| b.price.cmp(&a.price));
+        let mut top_bids = Vec::with_capacity(self.top_n);
+        while top_bids.len() < self.top_n {
+            let Some(entry) = heap.pop() else { break };
+            let (exchange, bids, fee) = sources[entry.src];
+            let bid = &bids[entry.idx];
+            top_bids.push(ExchangeBid {
+                exchange: exchange.clone(),
+                price: bid.price,
+                effective_price: entry.key,
+                amount: bid.amount,
+            });
+            if let Some(next) = bids.get(entry.idx + 1) {
+                heap.push(HeapEntry::new(
+                    next.price * (Decimal::ONE - fee),
+                    entry.src,
+                    entry.idx + 1,
+                ));
+            }
+        }
 
-        all_bids.into_iter().take(self.top_n).collect()
+        top_bids
     }
 
     // Updates the latest ask snapshot of the exchange and returns top 10 combined asks
@@ -115,29 +239,56 @@ impl MergedOrderBook {
     fn update_asks(&mut self, exchange: &str, asks: Vec<Ask>) -> Vec<ExchangeAsk> {
         self.latest_asks.insert(exchange.to_string(), asks);
 
-        let mut all_asks: Vec<ExchangeAsk> = Vec::new();
-
-        for (exchange, asks) in &mut self.latest_asks {
-            let list: Vec<ExchangeAsk> = asks
-                .into_iter()
-                .map(|b| ExchangeAsk { exchange: exchange.clone(), price: b.price, amount: b.amount })
-                .collect();
-            all_asks.extend(list.into_iter().take(self.top_n));
+        // Mirror of `update_bids`: asks are ordered by ascending effective price
+        // per venue, so a min-heap (a max-heap over reversed keys) yields the
+        // cheapest `top_n` across exchanges in O(top_n·log k).
+        let sources: Vec<(&String, &Vec<Ask>, Decimal)> = self
+            .latest_asks
+            .iter()
+            .map(|(exchange, asks)| (exchange, asks, self.fee(exchange)))
+            .collect();
+
+        let mut heap: BinaryHeap<Reverse<HeapEntry>> = BinaryHeap::new();
+        for (src, (_, asks, fee)) in sources.iter().enumerate() {
+            if let Some(ask) = asks.first() {
+                // An ask costs more once the taker fee is added.
+                heap.push(Reverse(HeapEntry::new(ask.price * (Decimal::ONE + *fee), src, 0)));
+            }
         }
 
-        all_asks.sort_by(|a, b| a.price.cmp(&b.price));
+        let mut top_asks = Vec::with_capacity(self.top_n);
+        while top_asks.len() < self.top_n {
+            let Some(Reverse(entry)) = heap.pop() else { break };
+            let (exchange, asks, fee) = sources[entry.src];
+            let ask = &asks[entry.idx];
+            top_asks.push(ExchangeAsk {
+                exchange: exchange.clone(),
+                price: ask.price,
+                effective_price: entry.key,
+                amount: ask.amount,
+            });
+            if let Some(next) = asks.get(entry.idx + 1) {
+                heap.push(Reverse(HeapEntry::new(
+                    next.price * (Decimal::ONE + fee),
+                    entry.src,
+                    entry.idx + 1,
+                )));
+            }
+        }
 
-        all_asks.into_iter().take(self.top_n).collect()
+        top_asks
     }
 
     pub fn update(&mut self, update: OrderBookUpdate) -> UpdateResult {
         let top_asks = self.update_asks(&update.exchange, update.asks);
         let top_bids = self.update_bids(&update.exchange, update.bids);
 
+        // The net spread is computed on fee-adjusted prices, so it reflects the
+        // real cross-venue arbitrage opportunity after trading costs.
         let spread: Option<Decimal> = top_asks.first().and_then(|ask| {
             top_bids
                 .first()
-                .map(|bid| ask.price - bid.price)
+                .map(|bid| ask.effective_price - bid.effective_price)
         });
 
         UpdateResult {
@@ -310,4 +461,36 @@ mod tests {
         assert_eq!(result2[2].price, dec!(101.2));
         assert_eq!(result2[2].amount, dec!(2.5));
     }
+
+    #[test]
+    fn test_fee_adjusted_spread() {
+        let mut merged_book = MergedOrderBook::new(3);
+        let mut fees = HashMap::new();
+        fees.insert("binance".to_owned(), dec!(0.001));
+        merged_book.set_fees(fees);
+
+        let update = OrderBookUpdate {
+            exchange: "binance".to_owned(),
+            bids: vec![Bid {
+                price: dec!(100.0),
+                amount: dec!(1.0),
+            }],
+            asks: vec![Ask {
+                price: dec!(101.0),
+                amount: dec!(1.0),
+            }],
+        };
+
+        let result = merged_book.update(update);
+
+        // An ask costs `price * (1 + fee)`, a bid yields `price * (1 - fee)`.
+        assert_eq!(result.asks[0].price, dec!(101.0));
+        assert_eq!(result.asks[0].effective_price, dec!(101.0) * (dec!(1) + dec!(0.001)));
+        assert_eq!(result.bids[0].price, dec!(100.0));
+        assert_eq!(result.bids[0].effective_price, dec!(100.0) * (dec!(1) - dec!(0.001)));
+
+        // The spread is the net, fee-adjusted cross-venue spread.
+        let expected = result.asks[0].effective_price - result.bids[0].effective_price;
+        assert_eq!(result.spread, Some(expected));
+    }
 }
@@ -0,0 +1,276 @@
+use std::collections::BTreeSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use tokio_postgres::{Client, NoTls};
+use tracing::{error, info};
+
+use crate::order_book::UpdateResult;
+
+/// Seconds since the Unix epoch for "now".
+pub fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Open/high/low/close accumulator for one time bucket.
+///
+/// Tracks candles for both the mid-price and the spread, plus the set of
+/// venues that contributed any level during the bucket so cross-venue coverage
+/// is visible in the persisted row.
+struct Accumulator {
+    bucket: i64,
+    mid: Ohlc,
+    spread: Ohlc,
+    exchanges: BTreeSet<String>,
+}
+
+#[derive(Clone, Copy)]
+struct Ohlc {
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+}
+
+impl Ohlc {
+    fn new(value: Decimal) -> Ohlc {
+        Ohlc {
+            open: value,
+            high: value,
+            low: value,
+            close: value,
+        }
+    }
+
+    fn update(&mut self, value: Decimal) {
+        self.high = self.high.max(value);
+        self.low = self.low.min(value);
+        self.close = value;
+    }
+}
+
+impl Accumulator {
+    fn new(bucket: i64, mid: Decimal, spread: Decimal, exchanges: BTreeSet<String>) -> Accumulator {
+        Accumulator {
+            bucket,
+            mid: Ohlc::new(mid),
+            spread: Ohlc::new(spread),
+            exchanges,
+        }
+    }
+
+    fn update(&mut self, mid: Decimal, spread: Decimal, exchanges: BTreeSet<String>) {
+        self.mid.update(mid);
+        self.spread.update(spread);
+        self.exchanges.extend(exchanges);
+    }
+}
+
+/// Writes raw snapshots and time-bucketed OHLC candles into Postgres.
+///
+/// Ingestion keeps one in-memory accumulator for the current bucket; when an
+/// update lands in a later bucket the completed candle is flushed with an
+/// upsert and a fresh accumulator starts. Every update is also appended to the
+/// `snapshots` table so [`Persistence::backfill`] can regenerate candles for a
+/// time range after downtime.
+pub struct Persistence {
+    client: Client,
+    interval: i64,
+    current: Option<Accumulator>,
+}
+
+impl Persistence {
+    /// Connects to Postgres, spawns the connection driver, and ensures the
+    /// schema exists. `interval` is the candle width in seconds.
+    pub async fn connect(url: &str, interval: i64) -> Result<Persistence> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                error!("postgres connection error: {}", e);
+            }
+        });
+
+        let persistence = Persistence {
+            client,
+            interval,
+            current: None,
+        };
+        persistence.init_schema().await?;
+        Ok(persistence)
+    }
+
+    async fn init_schema(&self) -> Result<()> {
+        self.client
+            .batch_execute(
+                "
+                CREATE TABLE IF NOT EXISTS snapshots (
+                    ts           BIGINT NOT NULL,
+                    mid          NUMERIC NOT NULL,
+                    spread       NUMERIC NOT NULL,
+                    exchanges    TEXT NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS snapshots_ts_idx ON snapshots (ts);
+
+                CREATE TABLE IF NOT EXISTS candles (
+                    bucket_start BIGINT PRIMARY KEY,
+                    mid_open     NUMERIC NOT NULL,
+                    mid_high     NUMERIC NOT NULL,
+                    mid_low      NUMERIC NOT NULL,
+                    mid_close    NUMERIC NOT NULL,
+                    spread_open  NUMERIC NOT NULL,
+                    spread_high  NUMERIC NOT NULL,
+                    spread_low   NUMERIC NOT NULL,
+                    spread_close NUMERIC NOT NULL,
+                    exchanges    TEXT NOT NULL
+                );
+                ",
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Appends one update to `snapshots` and folds it into the current candle,
+    /// flushing the previous bucket when `ts` crosses into a new one. Updates
+    /// missing either side (no mid/spread) are ignored.
+    pub async fn ingest(&mut self, result: &UpdateResult, ts: i64) -> Result<()> {
+        let (Some(mid), Some(spread)) = (result.mid(), result.spread) else {
+            return Ok(());
+        };
+        let exchanges = result.exchanges();
+
+        self.append_snapshot(ts, mid, spread, &exchanges).await?;
+
+        let bucket = ts.div_euclid(self.interval) * self.interval;
+
+        match &mut self.current {
+            Some(acc) if acc.bucket == bucket => {
+                acc.update(mid, spread, exchanges);
+            }
+            Some(_) => {
+                let completed = self
+                    .current
+                    .replace(Accumulator::new(bucket, mid, spread, exchanges))
+                    .expect("current accumulator present");
+                self.flush(&completed).await?;
+            }
+            None => {
+                self.current = Some(Accumulator::new(bucket, mid, spread, exchanges));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn append_snapshot(
+        &self,
+        ts: i64,
+        mid: Decimal,
+        spread: Decimal,
+        exchanges: &BTreeSet<String>,
+    ) -> Result<()> {
+        self.client
+            .execute(
+                "INSERT INTO snapshots (ts, mid, spread, exchanges) VALUES ($1, $2, $3, $4)",
+                &[&ts, &mid, &spread, &join(exchanges)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn flush(&self, acc: &Accumulator) -> Result<()> {
+        info!("flushing candle for bucket {}", acc.bucket);
+        self.client
+            .execute(
+                "INSERT INTO candles (
+                    bucket_start,
+                    mid_open, mid_high, mid_low, mid_close,
+                    spread_open, spread_high, spread_low, spread_close,
+                    exchanges
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (bucket_start) DO UPDATE SET
+                    mid_open = EXCLUDED.mid_open,
+                    mid_high = EXCLUDED.mid_high,
+                    mid_low = EXCLUDED.mid_low,
+                    mid_close = EXCLUDED.mid_close,
+                    spread_open = EXCLUDED.spread_open,
+                    spread_high = EXCLUDED.spread_high,
+                    spread_low = EXCLUDED.spread_low,
+                    spread_close = EXCLUDED.spread_close,
+                    exchanges = EXCLUDED.exchanges",
+                &[
+                    &acc.bucket,
+                    &acc.mid.open,
+                    &acc.mid.high,
+                    &acc.mid.low,
+                    &acc.mid.close,
+                    &acc.spread.open,
+                    &acc.spread.high,
+                    &acc.spread.low,
+                    &acc.spread.close,
+                    &join(&acc.exchanges),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Recomputes and upserts candles for `[from, to)` directly from the raw
+    /// `snapshots` table, so an operator can regenerate candles after downtime
+    /// without losing granularity.
+    pub async fn backfill(&self, from: i64, to: i64) -> Result<()> {
+        let rows = self
+            .client
+            .query(
+                "SELECT ts, mid, spread, exchanges FROM snapshots
+                 WHERE ts >= $1 AND ts < $2 ORDER BY ts ASC",
+                &[&from, &to],
+            )
+            .await?;
+
+        let mut current: Option<Accumulator> = None;
+
+        for row in rows {
+            let ts: i64 = row.get(0);
+            let mid: Decimal = row.get(1);
+            let spread: Decimal = row.get(2);
+            let exchanges: BTreeSet<String> = split(row.get::<_, String>(3));
+
+            let bucket = ts.div_euclid(self.interval) * self.interval;
+
+            match &mut current {
+                Some(acc) if acc.bucket == bucket => acc.update(mid, spread, exchanges),
+                Some(_) => {
+                    let completed = current
+                        .replace(Accumulator::new(bucket, mid, spread, exchanges))
+                        .expect("current accumulator present");
+                    self.flush(&completed).await?;
+                }
+                None => current = Some(Accumulator::new(bucket, mid, spread, exchanges)),
+            }
+        }
+
+        if let Some(acc) = current {
+            self.flush(&acc).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn join(exchanges: &BTreeSet<String>) -> String {
+    exchanges.iter().cloned().collect::<Vec<_>>().join(",")
+}
+
+fn split(exchanges: String) -> BTreeSet<String> {
+    exchanges
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .collect()
+}
@@ -0,0 +1,129 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::sse::{Event, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use futures_util::Stream;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt as _;
+use tracing::{error, info};
+
+use crate::order_book::{ExchangeAsk, ExchangeBid, UpdateResult};
+
+/// Price/amount/spread decimal places applied when formatting responses.
+///
+/// Rounding used to be hardcoded in `main`; keeping it here makes it a
+/// server-side presentation concern so ingestion can stay full-precision.
+const PRICE_DP: u32 = 2;
+const AMOUNT_DP: u32 = 4;
+const SPREAD_DP: u32 = 3;
+
+/// Shared, cloneable handle into the serving subsystem.
+///
+/// Holds the most recent merged book for the HTTP snapshot endpoints and a
+/// `broadcast` sender that fans each update out to any number of streaming
+/// subscribers, decoupling ingestion from output.
+#[derive(Clone)]
+pub struct MarketData {
+    latest: Arc<RwLock<Option<UpdateResult>>>,
+    updates: broadcast::Sender<UpdateResult>,
+}
+
+impl MarketData {
+    pub fn new() -> MarketData {
+        let (updates, _) = broadcast::channel(256);
+        MarketData {
+            latest: Arc::new(RwLock::new(None)),
+            updates,
+        }
+    }
+
+    /// Records the latest book and publishes it to streaming subscribers.
+    pub async fn publish(&self, update: UpdateResult) {
+        *self.latest.write().await = Some(update.clone());
+        // A send error only means there are currently no subscribers.
+        let _ = self.updates.send(update);
+    }
+
+    /// Starts the HTTP server on `addr`, serving `/tickers`, `/summary`, and an
+    /// SSE `/stream` of the broadcast feed.
+    pub fn serve(&self, addr: SocketAddr) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let app = Router::new()
+                .route("/tickers", get(tickers))
+                .route("/summary", get(summary))
+                .route("/stream", get(stream))
+                .with_state(state);
+
+            match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => {
+                    info!("Serving market data on {}", addr);
+                    if let Err(e) = axum::serve(listener, app).await {
+                        error!("server error: {}", e);
+                    }
+                }
+                Err(e) => error!("Cannot bind {}: {}", addr, e),
+            }
+        });
+    }
+}
+
+impl Default for MarketData {
+    fn default() -> Self {
+        MarketData::new()
+    }
+}
+
+/// A CoinGecko-style point-in-time view of the merged book.
+#[derive(Serialize)]
+struct Ticker {
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    best_bid: Option<Decimal>,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    best_ask: Option<Decimal>,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    spread: Option<Decimal>,
+    asks: Vec<ExchangeAsk>,
+    bids: Vec<ExchangeBid>,
+}
+
+impl From<UpdateResult> for Ticker {
+    fn from(mut result: UpdateResult) -> Ticker {
+        result.normalize(PRICE_DP, AMOUNT_DP, SPREAD_DP);
+        Ticker {
+            best_bid: result.best_bid(),
+            best_ask: result.best_ask(),
+            spread: result.spread,
+            asks: result.asks,
+            bids: result.bids,
+        }
+    }
+}
+
+async fn tickers(State(state): State<MarketData>) -> impl IntoResponse {
+    let latest = state.latest.read().await.clone();
+    Json(latest.map(Ticker::from))
+}
+
+async fn summary(State(state): State<MarketData>) -> impl IntoResponse {
+    // `/summary` mirrors `/tickers`; downstream consumers use whichever name
+    // their tooling expects.
+    tickers(State(state)).await
+}
+
+async fn stream(
+    State(state): State<MarketData>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let stream = BroadcastStream::new(state.updates.subscribe()).filter_map(|item| {
+        let update = item.ok()?;
+        Event::default().json_data(Ticker::from(update)).ok().map(Ok)
+    });
+    Sse::new(stream)
+}